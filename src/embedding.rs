@@ -0,0 +1,119 @@
+/// Represents the available models for the embeddings endpoint, kept separate from `Model`
+/// so embedding and chat models aren't conflated.
+pub enum EmbeddingModel {
+    TextEmbedding3Small,
+    TextEmbedding3Large,
+    TextEmbeddingAda002,
+}
+
+impl EmbeddingModel {
+    /// Formats the embedding model enum variant into the corresponding string representation.
+    pub(crate) fn format(&self) -> String {
+        String::from(match self {
+            EmbeddingModel::TextEmbedding3Small => "text-embedding-3-small",
+            EmbeddingModel::TextEmbedding3Large => "text-embedding-3-large",
+            EmbeddingModel::TextEmbeddingAda002 => "text-embedding-ada-002",
+        })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub(crate) struct EmbeddingsRequestBody {
+    pub(crate) model: String,
+    pub(crate) input: Vec<String>,
+}
+
+/// A deserialized response from the embeddings endpoint.
+#[derive(Debug)]
+pub struct EmbeddingsResponse {
+    /// One embedding vector per input string, in the same order as the request's `input`.
+    pub embeddings: Vec<Vec<f32>>,
+    /// Token accounting for the request.
+    pub usage: EmbeddingUsage,
+}
+
+/// Token accounting for an embeddings request.
+#[derive(serde::Deserialize, Debug)]
+pub struct EmbeddingUsage {
+    /// The number of tokens in the input strings.
+    pub prompt_tokens: u32,
+    /// Equal to `prompt_tokens`, since embeddings requests have no completion tokens.
+    pub total_tokens: u32,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct RawEmbeddingsResponse {
+    data: Vec<RawEmbeddingData>,
+    usage: EmbeddingUsage,
+}
+
+#[derive(serde::Deserialize)]
+struct RawEmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+impl From<RawEmbeddingsResponse> for EmbeddingsResponse {
+    fn from(mut raw: RawEmbeddingsResponse) -> Self {
+        raw.data.sort_by_key(|d| d.index);
+
+        EmbeddingsResponse {
+            embeddings: raw.data.into_iter().map(|d| d.embedding).collect(),
+            usage: raw.usage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_model_format() {
+        assert_eq!(
+            EmbeddingModel::TextEmbedding3Small.format(),
+            "text-embedding-3-small"
+        );
+        assert_eq!(
+            EmbeddingModel::TextEmbedding3Large.format(),
+            "text-embedding-3-large"
+        );
+        assert_eq!(
+            EmbeddingModel::TextEmbeddingAda002.format(),
+            "text-embedding-ada-002"
+        );
+    }
+
+    #[test]
+    fn test_raw_embeddings_response_into_embeddings_response() {
+        let body = serde_json::json!({
+            "data": [
+                {"embedding": [0.1, 0.2], "index": 0},
+                {"embedding": [0.3, 0.4], "index": 1},
+            ],
+            "usage": {"prompt_tokens": 5, "total_tokens": 5},
+        });
+
+        let raw: RawEmbeddingsResponse = serde_json::from_value(body).unwrap();
+        let response: EmbeddingsResponse = raw.into();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+        assert_eq!(response.usage.total_tokens, 5);
+    }
+
+    #[test]
+    fn test_raw_embeddings_response_reorders_by_index() {
+        let body = serde_json::json!({
+            "data": [
+                {"embedding": [0.3, 0.4], "index": 1},
+                {"embedding": [0.1, 0.2], "index": 0},
+            ],
+            "usage": {"prompt_tokens": 5, "total_tokens": 5},
+        });
+
+        let raw: RawEmbeddingsResponse = serde_json::from_value(body).unwrap();
+        let response: EmbeddingsResponse = raw.into();
+
+        assert_eq!(response.embeddings, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// The `{"error": {"message", "type", "code"}}` payload OpenAI returns on 4xx/5xx responses.
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct ApiError {
+    /// A human-readable description of the error.
+    pub message: String,
+    /// The category of error, e.g. `invalid_request_error` or `rate_limit_exceeded`.
+    #[serde(rename = "type")]
+    pub error_type: String,
+    /// A short machine-readable error code, when OpenAI provides one.
+    pub code: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ApiErrorBody {
+    pub(crate) error: ApiError,
+}
+
+/// The error type returned by `Openai`'s request methods.
+#[derive(Debug)]
+pub enum OpenaiError {
+    /// The request could not be sent, or the response could not be read off the wire.
+    Transport(reqwest::Error),
+    /// OpenAI responded with a non-2xx status and an `{"error": ...}` payload.
+    Api(ApiError),
+    /// The response body was not valid JSON, or didn't match the expected shape.
+    Parse(serde_json::Error),
+    /// The response didn't contain any choices to read a message from.
+    EmptyChoices,
+}
+
+impl fmt::Display for OpenaiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenaiError::Transport(err) => write!(f, "transport error: {}", err),
+            OpenaiError::Api(err) => write!(f, "OpenAI API error ({}): {}", err.error_type, err.message),
+            OpenaiError::Parse(err) => write!(f, "failed to parse response: {}", err),
+            OpenaiError::EmptyChoices => write!(f, "response contained no choices"),
+        }
+    }
+}
+
+impl std::error::Error for OpenaiError {}
+
+impl From<reqwest::Error> for OpenaiError {
+    fn from(err: reqwest::Error) -> Self {
+        OpenaiError::Transport(err)
+    }
+}
+
+impl From<serde_json::Error> for OpenaiError {
+    fn from(err: serde_json::Error) -> Self {
+        OpenaiError::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_body_deserialization() {
+        let body = serde_json::json!({
+            "error": {
+                "message": "Rate limit reached",
+                "type": "rate_limit_exceeded",
+                "code": "rate_limit_exceeded",
+            }
+        });
+
+        let error_body: ApiErrorBody = serde_json::from_value(body).unwrap();
+        assert_eq!(error_body.error.message, "Rate limit reached");
+        assert_eq!(error_body.error.error_type, "rate_limit_exceeded");
+        assert_eq!(error_body.error.code.as_deref(), Some("rate_limit_exceeded"));
+    }
+}
@@ -1,6 +1,29 @@
-use reqwest::{Error, Response};
+mod embedding;
+mod error;
+mod request;
+mod response;
+mod retry;
+
+pub use embedding::{EmbeddingModel, EmbeddingUsage, EmbeddingsResponse};
+pub use error::{ApiError, OpenaiError};
+pub use request::ChatCompletionRequest;
+pub use response::{ChatCompletionResponse, Choice, Usage};
+pub use retry::RetryPolicy;
+
+use embedding::{EmbeddingsRequestBody, RawEmbeddingsResponse};
+use error::ApiErrorBody;
+use futures_core::Stream;
+use futures_util::StreamExt;
+use reqwest::Response;
+use retry::send_with_retry;
 use serde_json::Value;
 
+/// The SSE sentinel OpenAI sends to mark the end of a streamed chat completion.
+const STREAM_DONE_SENTINEL: &str = "[DONE]";
+
+/// The default OpenAI API base URL used when no other base URL is configured.
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+
 /// Represents the available models for the OpenAI instance.
 pub enum Model {
     Gpt35Turbo,
@@ -12,9 +35,14 @@ pub enum Model {
     Gpt432k,
     Gpt4Instruct,
     Gpt432k0613,
+    /// A model name that isn't one of the fixed variants above, passed through verbatim.
+    ///
+    /// Useful for OpenAI-compatible servers (Ollama, perplexity.ai, Azure OpenAI, ...) that
+    /// expose model names such as `mistralai/Mixtral-8x7B-Instruct-v0.1`.
+    Custom(String),
 }
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
 /// Represents a message with a role and content.
 pub struct Message {
     /// The role of the message.
@@ -29,6 +57,10 @@ pub struct Openai {
     model: Model,
     /// The API key used for authentication.
     api_key: String,
+    /// The base URL chat completion and embeddings requests are sent to.
+    base_url: String,
+    /// The retry policy applied to transient (`429`/`5xx`) failures.
+    retry_policy: RetryPolicy,
 }
 
 /// Implementation of the `Openai` struct.
@@ -47,51 +79,111 @@ impl Openai {
     /// # Example
     ///
     /// ```
-    /// use rust-openai-lib::{ Model, Openai};
-    ///
-    /// #[tokio::main]
-    /// fn main() {
-    ///
-    /// let api_key = String::new("your_api_key");
+    /// use rust_openai_lib::{Model, Openai};
     ///
+    /// let api_key = "your_api_key".to_string();
     /// let openai = Openai::new(api_key, Model::Gpt35Turbo);
-    /// }
     /// ```
     pub fn new(api_key: String, model: Model) -> Self {
-        Openai { model, api_key }
+        Openai {
+            model,
+            api_key,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+        }
     }
 
-    /// Sends a request to the OpenAI API to get chat completions.
+    /// Creates a new instance of `Openai` pointed at a custom base URL.
+    ///
+    /// This is useful for talking to OpenAI-compatible servers such as a local Ollama
+    /// instance, Azure OpenAI, or perplexity.ai instead of the public OpenAI endpoint.
     ///
     /// # Arguments
     ///
-    /// * `messages` - The list of messages for the chat completion.
+    /// * `api_key` - The API key for accessing the API.
+    /// * `model` - The model to be used for chat completions.
+    /// * `base_url` - The base URL of the API, e.g. `http://localhost:11434/v1`.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the json'ed response from the API or an error.
+    /// A new instance of `Openai`.
     ///
     /// # Example
     ///
     /// ```
-    /// use rust-openai-lib::{Message, Model, Openai};
+    /// use rust_openai_lib::{Model, Openai};
     ///
-    /// #[tokio::main]
-    /// fn main() {
+    /// let api_key = "your_api_key".to_string();
+    /// let openai = Openai::new_with_base_url(
+    ///     api_key,
+    ///     Model::Custom("mistralai/Mixtral-8x7B-Instruct-v0.1".to_string()),
+    ///     "http://localhost:11434/v1".to_string(),
+    /// );
+    /// ```
+    pub fn new_with_base_url(api_key: String, model: Model, base_url: String) -> Self {
+        Openai {
+            model,
+            api_key,
+            base_url,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Overrides the retry policy used for transient (`429`/`5xx`) failures.
+    ///
+    /// # Example
     ///
-    /// let api_key = String::new("your_api_key");
+    /// ```
+    /// use rust_openai_lib::{Model, Openai, RetryPolicy};
+    /// use std::time::Duration;
     ///
-    /// let openai = Openai::new(api_key, Model::Gpt35Turbo);
+    /// let api_key = "your_api_key".to_string();
+    /// let openai = Openai::new(api_key, Model::Gpt35Turbo).with_retry_policy(RetryPolicy {
+    ///     max_retries: 5,
+    ///     base_delay: Duration::from_millis(250),
+    ///     max_delay: Duration::from_secs(10),
+    /// });
+    /// ```
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends a request to the OpenAI API to get chat completions.
     ///
-    /// let messages = vec![Message {
-    ///     role: "user".to_string(),
-    ///     content: "Hello, I'm a user!".to_string(),
-    /// }]
+    /// # Arguments
     ///
-    /// let response = openai.get_chat_completion(messages).await.unwrap();
+    /// * `messages` - The list of messages for the chat completion.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the deserialized `ChatCompletionResponse`, or an `OpenaiError` if
+    /// the request failed to send, OpenAI returned an error payload, or the response body
+    /// couldn't be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_openai_lib::{Message, Model, Openai};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let api_key = "your_api_key".to_string();
+    ///     let openai = Openai::new(api_key, Model::Gpt35Turbo);
+    ///
+    ///     let messages = vec![Message {
+    ///         role: "user".to_string(),
+    ///         content: "Hello, I'm a user!".to_string(),
+    ///     }];
+    ///
+    ///     let response = openai.get_chat_completion(messages).await.unwrap();
+    ///     println!("{}", response.choices[0].message.content);
     /// }
     /// ```
-    pub async fn get_chat_completion(&self, messages: Vec<Message>) -> Result<Value, Error> {
+    pub async fn get_chat_completion(
+        &self,
+        messages: Vec<Message>,
+    ) -> Result<ChatCompletionResponse, OpenaiError> {
         let client = reqwest::Client::new();
 
         #[derive(serde::Serialize)]
@@ -105,22 +197,298 @@ impl Openai {
             messages,
         };
 
-        let url = "https://api.openai.com/v1/chat/completions";
+        let url = format!("{}/chat/completions", self.base_url);
 
-        let response = client
-            .post(url)
-            .json(&body)
-            .header("Content-Type", "application/json")
-            .bearer_auth(&self.api_key)
-            .send()
-            .await;
+        let raw_response = send_with_retry(&self.retry_policy, || {
+            client
+                .post(&url)
+                .json(&body)
+                .header("Content-Type", "application/json")
+                .bearer_auth(&self.api_key)
+        })
+        .await?;
+
+        parse_chat_completion_response(raw_response).await
+    }
+
+    /// Sends a request to the OpenAI API to get chat completions, streamed token by token.
+    ///
+    /// The response is read as a stream of Server-Sent Events: each line of the form
+    /// `data: { ... }` holds a JSON chunk whose `choices[0].delta.content` is the next
+    /// fragment of the assistant's reply. The stream ends when the `data: [DONE]` sentinel
+    /// is received.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The list of messages for the chat completion.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Stream` of content deltas, or an error if the request
+    /// could not be sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_openai_lib::{Message, Model, Openai};
+    /// use futures_util::{pin_mut, StreamExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let api_key = "your_api_key".to_string();
+    ///     let openai = Openai::new(api_key, Model::Gpt35Turbo);
+    ///
+    ///     let messages = vec![Message {
+    ///         role: "user".to_string(),
+    ///         content: "Hello, I'm a user!".to_string(),
+    ///     }];
+    ///
+    ///     let stream = openai.get_chat_completion_stream(messages).await.unwrap();
+    ///     pin_mut!(stream);
+    ///     while let Some(delta) = stream.next().await {
+    ///         print!("{}", delta.unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_chat_completion_stream(
+        &self,
+        messages: Vec<Message>,
+    ) -> Result<impl Stream<Item = Result<String, OpenaiError>>, OpenaiError> {
+        let client = reqwest::Client::new();
+
+        #[derive(serde::Serialize)]
+        struct ChatCompletionStreamBody {
+            model: String,
+            messages: Vec<Message>,
+            stream: bool,
+        }
+
+        let body = ChatCompletionStreamBody {
+            model: self.model.format(),
+            messages,
+            stream: true,
+        };
+
+        let url = format!("{}/chat/completions", self.base_url);
+
+        let raw_response = send_with_retry(&self.retry_policy, || {
+            client
+                .post(&url)
+                .json(&body)
+                .header("Content-Type", "application/json")
+                .bearer_auth(&self.api_key)
+        })
+        .await?;
+
+        let status = raw_response.status();
+        if !status.is_success() {
+            let text = raw_response.text().await?;
+            let error_body: ApiErrorBody = serde_json::from_str(&text)?;
+            return Err(OpenaiError::Api(error_body.error));
+        }
+
+        Ok(sse_content_stream(raw_response))
+    }
+
+    /// Sends a fully-built `ChatCompletionRequest` to the OpenAI API.
+    ///
+    /// Unlike `get_chat_completion`, this gives callers control over the full set of
+    /// sampling/control parameters (temperature, top_p, max_tokens, stop sequences, ...)
+    /// exposed by the `ChatCompletionRequest` builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request built via `ChatCompletionRequest::new` and its builder methods.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the deserialized `ChatCompletionResponse`, or an `OpenaiError` if
+    /// the request failed to send, OpenAI returned an error payload, or the response body
+    /// couldn't be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_openai_lib::{ChatCompletionRequest, Message, Model, Openai};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let api_key = "your_api_key".to_string();
+    ///     let openai = Openai::new(api_key, Model::Gpt35Turbo);
+    ///
+    ///     let messages = vec![Message {
+    ///         role: "user".to_string(),
+    ///         content: "Hello, I'm a user!".to_string(),
+    ///     }];
+    ///
+    ///     let request = ChatCompletionRequest::new(Model::Gpt35Turbo, messages).temperature(0.2);
+    ///     let response = openai.send(request).await.unwrap();
+    /// }
+    /// ```
+    pub async fn send(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, OpenaiError> {
+        let client = reqwest::Client::new();
+
+        let url = format!("{}/chat/completions", self.base_url);
+
+        let raw_response = send_with_retry(&self.retry_policy, || {
+            client
+                .post(&url)
+                .json(&request)
+                .header("Content-Type", "application/json")
+                .bearer_auth(&self.api_key)
+        })
+        .await?;
+
+        parse_chat_completion_response(raw_response).await
+    }
+
+    /// Sends a request to the OpenAI API to get embeddings for a batch of input strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The strings to embed.
+    /// * `model` - The embedding model to use.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an `EmbeddingsResponse` with one embedding vector per input
+    /// string, in the same order as `input`, or an `OpenaiError`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rust_openai_lib::{EmbeddingModel, Model, Openai};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let api_key = "your_api_key".to_string();
+    ///     let openai = Openai::new(api_key, Model::Gpt35Turbo);
+    ///
+    ///     let response = openai
+    ///         .get_embeddings(vec!["hello world".to_string()], EmbeddingModel::TextEmbedding3Small)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     println!("{:?}", response.embeddings[0]);
+    /// }
+    /// ```
+    pub async fn get_embeddings(
+        &self,
+        input: Vec<String>,
+        model: EmbeddingModel,
+    ) -> Result<EmbeddingsResponse, OpenaiError> {
+        let client = reqwest::Client::new();
+
+        let body = EmbeddingsRequestBody {
+            model: model.format(),
+            input,
+        };
+
+        let url = format!("{}/embeddings", self.base_url);
+
+        let raw_response = send_with_retry(&self.retry_policy, || {
+            client
+                .post(&url)
+                .json(&body)
+                .header("Content-Type", "application/json")
+                .bearer_auth(&self.api_key)
+        })
+        .await?;
 
-        match response {
-            Ok(raw_response) => {
-                let parsed_response = raw_response.json::<serde_json::Value>().await;
-                Ok(parsed_response?)
+        parse_embeddings_response(raw_response).await
+    }
+}
+
+/// Reads an embeddings response body, returning an `OpenaiError::Api` if OpenAI responded
+/// with an error payload instead of a successful result.
+async fn parse_embeddings_response(raw_response: Response) -> Result<EmbeddingsResponse, OpenaiError> {
+    let status = raw_response.status();
+    let text = raw_response.text().await?;
+
+    if !status.is_success() {
+        let error_body: ApiErrorBody = serde_json::from_str(&text)?;
+        return Err(OpenaiError::Api(error_body.error));
+    }
+
+    let raw: RawEmbeddingsResponse = serde_json::from_str(&text)?;
+    Ok(raw.into())
+}
+
+/// Reads a chat completion response body, returning an `OpenaiError::Api` if OpenAI responded
+/// with an error payload instead of a successful completion.
+async fn parse_chat_completion_response(
+    raw_response: Response,
+) -> Result<ChatCompletionResponse, OpenaiError> {
+    let status = raw_response.status();
+    let text = raw_response.text().await?;
+
+    if !status.is_success() {
+        let error_body: ApiErrorBody = serde_json::from_str(&text)?;
+        return Err(OpenaiError::Api(error_body.error));
+    }
+
+    let raw: Value = serde_json::from_str(&text)?;
+    let response: ChatCompletionResponse = serde_json::from_value(raw.clone())?;
+    Ok(response.with_raw(raw))
+}
+
+/// Turns the raw SSE body of a streamed chat completion response into a stream of
+/// content deltas, buffering partial lines that are split across network chunks.
+fn sse_content_stream(response: Response) -> impl Stream<Item = Result<String, OpenaiError>> {
+    sse_content_stream_from_bytes(response.bytes_stream().map(|chunk| chunk.map_err(OpenaiError::from)))
+}
+
+/// Does the actual SSE line-buffering and parsing behind `sse_content_stream`, generic over
+/// the byte-chunk stream so the logic can be exercised with synthetic chunks in tests instead
+/// of a live `reqwest::Response`.
+fn sse_content_stream_from_bytes<S, B>(
+    mut bytes_stream: S,
+) -> impl Stream<Item = Result<String, OpenaiError>>
+where
+    S: Stream<Item = Result<B, OpenaiError>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    async_stream::stream! {
+        // Buffered as raw bytes, not `String`: a chunk boundary has no reason to respect a
+        // UTF-8 codepoint boundary, so decoding must wait until a full line (and therefore
+        // all the bytes of any multi-byte character in it) has been assembled.
+        let mut buffer: Vec<u8> = Vec::new();
+
+        while let Some(chunk) = bytes_stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    yield Err(err);
+                    continue;
+                }
+            };
+
+            buffer.extend_from_slice(chunk.as_ref());
+
+            while let Some(newline_index) = buffer.iter().position(|&byte| byte == b'\n') {
+                let line_bytes: Vec<u8> = buffer.drain(..=newline_index).collect();
+                let line = String::from_utf8_lossy(&line_bytes).trim().to_string();
+
+                let Some(data) = line.strip_prefix("data: ") else {
+                    continue;
+                };
+
+                if data == STREAM_DONE_SENTINEL {
+                    return;
+                }
+
+                let chunk: Value = match serde_json::from_str(data) {
+                    Ok(chunk) => chunk,
+                    Err(_) => continue,
+                };
+
+                if let Some(delta) = chunk["choices"][0]["delta"]["content"].as_str() {
+                    yield Ok(delta.to_string());
+                }
             }
-            Err(err) => Err(err),
         }
     }
 }
@@ -131,24 +499,26 @@ impl Model {
     /// # Returns
     ///
     /// The formatted string representation of the model.
-    fn format(&self) -> String {
-        return String::from(match self {
-            Model::Gpt35Turbo => "gpt-3.5-turbo",
-            Model::Gpt35Turbo16k => "gpt-3.5-turbo-16k",
-            Model::Gpt35TurboInstruct => "gpt-3.5-turbo-instruct",
-            Model::Gpt35Turbo1106 => "gpt-3.5-turbo-1106",
-            Model::Gpt41106Preview => "gpt-4-1106-preview",
-            Model::Gpt4 => "gpt-4",
-            Model::Gpt432k => "gpt-4-32k",
-            Model::Gpt4Instruct => "gpt-4-instruct",
-            Model::Gpt432k0613 => "gpt-4-32k-0613",
-        });
+    pub(crate) fn format(&self) -> String {
+        match self {
+            Model::Gpt35Turbo => String::from("gpt-3.5-turbo"),
+            Model::Gpt35Turbo16k => String::from("gpt-3.5-turbo-16k"),
+            Model::Gpt35TurboInstruct => String::from("gpt-3.5-turbo-instruct"),
+            Model::Gpt35Turbo1106 => String::from("gpt-3.5-turbo-1106"),
+            Model::Gpt41106Preview => String::from("gpt-4-1106-preview"),
+            Model::Gpt4 => String::from("gpt-4"),
+            Model::Gpt432k => String::from("gpt-4-32k"),
+            Model::Gpt4Instruct => String::from("gpt-4-instruct"),
+            Model::Gpt432k0613 => String::from("gpt-4-32k-0613"),
+            Model::Custom(name) => name.clone(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Model, Openai};
+    use crate::{sse_content_stream_from_bytes, Model, Openai, OpenaiError, RetryPolicy, DEFAULT_BASE_URL};
+    use futures_util::StreamExt;
 
     #[test]
     fn test_model_format() {
@@ -161,6 +531,10 @@ mod tests {
         assert_eq!(Model::Gpt432k.format(), "gpt-4-32k");
         assert_eq!(Model::Gpt4Instruct.format(), "gpt-4-instruct");
         assert_eq!(Model::Gpt432k0613.format(), "gpt-4-32k-0613");
+        assert_eq!(
+            Model::Custom("mistralai/Mixtral-8x7B-Instruct-v0.1".to_string()).format(),
+            "mistralai/Mixtral-8x7B-Instruct-v0.1"
+        );
     }
 
     #[test]
@@ -168,5 +542,82 @@ mod tests {
         let openai = Openai::new("test_api_key".to_string(), Model::Gpt4);
         assert_eq!(openai.api_key, "test_api_key");
         assert_eq!(openai.model.format(), "gpt-4");
+        assert_eq!(openai.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn test_openai_new_with_base_url() {
+        let openai = Openai::new_with_base_url(
+            "test_api_key".to_string(),
+            Model::Gpt4,
+            "http://localhost:11434/v1".to_string(),
+        );
+        assert_eq!(openai.api_key, "test_api_key");
+        assert_eq!(openai.base_url, "http://localhost:11434/v1");
+    }
+
+    #[test]
+    fn test_openai_with_retry_policy() {
+        let openai = Openai::new("test_api_key".to_string(), Model::Gpt4).with_retry_policy(
+            RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            },
+        );
+        assert_eq!(openai.retry_policy.max_retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sse_content_stream_yields_deltas_split_across_chunks() {
+        let chunks: Vec<Result<&str, OpenaiError>> = vec![
+            Ok("data: {\"choices\":[{\"delta\":{\"content\":\"Hel"),
+            Ok("lo\"}}]}\ndata: {\"choices\":[{\"delta\":{\"content\":\" world\"}}]}\n"),
+            Ok("data: [DONE]\n"),
+            Ok("data: {\"choices\":[{\"delta\":{\"content\":\"should not be yielded\"}}]}\n"),
+        ];
+
+        let deltas: Vec<String> = sse_content_stream_from_bytes(futures_util::stream::iter(chunks))
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(deltas, vec!["Hello".to_string(), " world".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_sse_content_stream_skips_malformed_json_and_non_data_lines() {
+        let chunks: Vec<Result<&str, OpenaiError>> = vec![
+            Ok(": this is a comment, not a data line\n"),
+            Ok("data: not valid json\n"),
+            Ok("data: {\"choices\":[{\"delta\":{\"content\":\"ok\"}}]}\n"),
+            Ok("data: [DONE]\n"),
+        ];
+
+        let deltas: Vec<String> = sse_content_stream_from_bytes(futures_util::stream::iter(chunks))
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(deltas, vec!["ok".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_sse_content_stream_handles_multibyte_utf8_split_across_chunks() {
+        // "café" encoded as UTF-8, with the 2-byte 'é' (0xC3 0xA9) split across two chunks.
+        let line = "data: {\"choices\":[{\"delta\":{\"content\":\"caf\u{e9}\"}}]}\n".as_bytes();
+        let split_at = line.iter().position(|&byte| byte == 0xC3).unwrap() + 1;
+
+        let chunks: Vec<Result<Vec<u8>, OpenaiError>> = vec![
+            Ok(line[..split_at].to_vec()),
+            Ok(line[split_at..].to_vec()),
+            Ok(b"data: [DONE]\n".to_vec()),
+        ];
+
+        let deltas: Vec<String> = sse_content_stream_from_bytes(futures_util::stream::iter(chunks))
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(deltas, vec!["caf\u{e9}".to_string()]);
     }
 }
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::{Message, Model};
+
+/// A request to the chat completions endpoint, built incrementally via the builder
+/// methods below.
+///
+/// `model` and `messages` are required; every other field is an optional
+/// sampling/control parameter and is left out of the serialized request body when unset.
+#[derive(serde::Serialize)]
+pub struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logit_bias: Option<HashMap<String, i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+#[derive(serde::Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    format_type: String,
+}
+
+impl ChatCompletionRequest {
+    /// Creates a new chat completion request for the given model and messages, with every
+    /// optional sampling parameter left unset.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model to be used for the chat completion.
+    /// * `messages` - The list of messages for the chat completion.
+    pub fn new(model: Model, messages: Vec<Message>) -> Self {
+        ChatCompletionRequest {
+            model: model.format(),
+            messages,
+            temperature: None,
+            top_p: None,
+            n: None,
+            max_tokens: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            logit_bias: None,
+            user: None,
+            response_format: None,
+        }
+    }
+
+    /// Sets the sampling temperature, between 0 and 2. Higher values make output more random.
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Sets the nucleus sampling probability mass, as an alternative to `temperature`.
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Sets how many chat completion choices to generate for each input message.
+    pub fn n(mut self, n: u32) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    /// Sets the maximum number of tokens to generate in the completion.
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets up to four sequences where the API will stop generating further tokens.
+    pub fn stop(mut self, stop: Vec<String>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Penalizes new tokens based on whether they already appear in the text so far,
+    /// increasing the model's likelihood to talk about new topics.
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Penalizes new tokens based on their existing frequency in the text so far,
+    /// decreasing the model's likelihood to repeat itself.
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Biases the likelihood of specified tokens appearing in the completion, mapping
+    /// token IDs (as strings) to a bias value between -100 and 100.
+    pub fn logit_bias(mut self, logit_bias: HashMap<String, i32>) -> Self {
+        self.logit_bias = Some(logit_bias);
+        self
+    }
+
+    /// Sets a unique identifier representing the end-user, to help OpenAI monitor and
+    /// detect abuse.
+    pub fn user(mut self, user: String) -> Self {
+        self.user = Some(user);
+        self
+    }
+
+    /// Requests that OpenAI guarantee the assistant's message content is syntactically
+    /// valid JSON.
+    ///
+    /// The prompt must still instruct the model what JSON shape to produce (e.g. "Respond
+    /// only with a JSON object of the form `{...}`") — this only enforces that whatever
+    /// comes back parses as JSON. Pair with `ChatCompletionResponse::parse_json` to
+    /// deserialize the result into a caller-provided type.
+    pub fn json_mode(mut self) -> Self {
+        self.response_format = Some(ResponseFormat {
+            format_type: "json_object".to_string(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_completion_request_defaults_to_no_optional_fields() {
+        let request = ChatCompletionRequest::new(
+            Model::Gpt35Turbo,
+            vec![Message {
+                role: "user".to_string(),
+                content: "hi".to_string(),
+            }],
+        );
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert_eq!(serialized["model"], "gpt-3.5-turbo");
+        assert!(serialized.get("temperature").is_none());
+        assert!(serialized.get("max_tokens").is_none());
+    }
+
+    #[test]
+    fn test_chat_completion_request_builder_sets_fields() {
+        let request = ChatCompletionRequest::new(
+            Model::Gpt4,
+            vec![Message {
+                role: "user".to_string(),
+                content: "hi".to_string(),
+            }],
+        )
+        .temperature(0.2)
+        .max_tokens(256)
+        .user("user-123".to_string());
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert_eq!(serialized["temperature"], 0.2_f32 as f64);
+        assert_eq!(serialized["max_tokens"], 256);
+        assert_eq!(serialized["user"], "user-123");
+    }
+
+    #[test]
+    fn test_chat_completion_request_json_mode() {
+        let request = ChatCompletionRequest::new(
+            Model::Gpt4,
+            vec![Message {
+                role: "user".to_string(),
+                content: "Respond with {\"answer\": 42}".to_string(),
+            }],
+        )
+        .json_mode();
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert_eq!(serialized["response_format"]["type"], "json_object");
+    }
+}
@@ -0,0 +1,136 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Message, OpenaiError};
+
+/// A deserialized response from the chat completions endpoint.
+#[derive(serde::Deserialize, Debug)]
+pub struct ChatCompletionResponse {
+    /// The unique identifier OpenAI assigned to this completion.
+    pub id: String,
+    /// The model that generated the completion.
+    pub model: String,
+    /// The generated completion choices, one per requested `n`.
+    pub choices: Vec<Choice>,
+    /// Token accounting for the request and its completion.
+    pub usage: Usage,
+    /// The raw JSON response body, kept around for fields not yet modeled by this struct.
+    #[serde(skip)]
+    raw: Value,
+}
+
+impl ChatCompletionResponse {
+    /// Returns the raw JSON response body, for accessing fields this struct doesn't expose yet.
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+
+    pub(crate) fn with_raw(mut self, raw: Value) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Deserializes the first choice's message content into `T`.
+    ///
+    /// Intended for use with responses requested via `ChatCompletionRequest::json_mode`,
+    /// which guarantees the content is syntactically valid JSON. Returns
+    /// `OpenaiError::EmptyChoices` if the response has no choices, or `OpenaiError::Parse`
+    /// if the content doesn't deserialize into `T`.
+    pub fn parse_json<T: DeserializeOwned>(&self) -> Result<T, OpenaiError> {
+        let content = &self
+            .choices
+            .first()
+            .ok_or(OpenaiError::EmptyChoices)?
+            .message
+            .content;
+
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+/// One generated completion choice.
+#[derive(serde::Deserialize, Debug)]
+pub struct Choice {
+    /// The index of this choice within the response's `choices` array.
+    pub index: u32,
+    /// The message generated for this choice.
+    pub message: Message,
+    /// Why the model stopped generating tokens, e.g. `"stop"` or `"length"`.
+    pub finish_reason: Option<String>,
+}
+
+/// Token accounting for a chat completion request.
+#[derive(serde::Deserialize, Debug)]
+pub struct Usage {
+    /// The number of tokens in the input messages.
+    pub prompt_tokens: u32,
+    /// The number of tokens in the generated completion.
+    pub completion_tokens: u32,
+    /// `prompt_tokens + completion_tokens`.
+    pub total_tokens: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_completion_response_deserialization() {
+        let body = serde_json::json!({
+            "id": "chatcmpl-123",
+            "model": "gpt-3.5-turbo",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hello!"},
+                "finish_reason": "stop",
+            }],
+            "usage": {
+                "prompt_tokens": 9,
+                "completion_tokens": 3,
+                "total_tokens": 12,
+            },
+        });
+
+        let response: ChatCompletionResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(response.id, "chatcmpl-123");
+        assert_eq!(response.choices[0].message.content, "Hello!");
+        assert_eq!(response.usage.total_tokens, 12);
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Answer {
+        answer: u32,
+    }
+
+    #[test]
+    fn test_parse_json_deserializes_message_content() {
+        let body = serde_json::json!({
+            "id": "chatcmpl-123",
+            "model": "gpt-3.5-turbo",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "{\"answer\": 42}"},
+                "finish_reason": "stop",
+            }],
+            "usage": {"prompt_tokens": 9, "completion_tokens": 3, "total_tokens": 12},
+        });
+
+        let response: ChatCompletionResponse = serde_json::from_value(body).unwrap();
+        let answer: Answer = response.parse_json().unwrap();
+        assert_eq!(answer, Answer { answer: 42 });
+    }
+
+    #[test]
+    fn test_parse_json_fails_on_empty_choices() {
+        let body = serde_json::json!({
+            "id": "chatcmpl-123",
+            "model": "gpt-3.5-turbo",
+            "choices": [],
+            "usage": {"prompt_tokens": 9, "completion_tokens": 0, "total_tokens": 9},
+        });
+
+        let response: ChatCompletionResponse = serde_json::from_value(body).unwrap();
+        let result: Result<Answer, OpenaiError> = response.parse_json();
+        assert!(matches!(result, Err(OpenaiError::EmptyChoices)));
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{header::HeaderMap, Response, StatusCode};
+
+/// Configures automatic retries for transient failures (`429 Too Many Requests` and `5xx`
+/// responses) on `Openai`'s request methods.
+///
+/// Retries use exponential backoff with jitter: the delay before retry `n` (counting from 0)
+/// is a random duration between 0 and `min(base_delay * 2^n, max_delay)`. When the response
+/// carries a `Retry-After` header given as a whole number of seconds, that value is honored
+/// instead; a `Retry-After` given as an HTTP date is not parsed, and the backoff delay is used.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// How many times to retry a failed request before giving up and returning the last error.
+    pub max_retries: u32,
+    /// The backoff delay used for the first retry.
+    pub base_delay: Duration,
+    /// The maximum backoff delay, regardless of how many retries have been attempted.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Disables automatic retries; the first failure is returned to the caller.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+}
+
+/// Sends a request built by `build_request`, retrying on `429`/`5xx` responses according to
+/// `policy`. Returns the last response once retries are exhausted or a non-retryable status
+/// (including success) is received.
+pub(crate) async fn send_with_retry(
+    policy: &RetryPolicy,
+    build_request: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<Response, reqwest::Error> {
+    let mut attempt = 0;
+
+    loop {
+        let response = build_request().send().await?;
+        let status = response.status();
+
+        if !should_retry(status, attempt, policy) {
+            return Ok(response);
+        }
+
+        let delay =
+            retry_after_delay(response.headers()).unwrap_or_else(|| backoff_delay(policy, attempt));
+        attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Decides whether `send_with_retry` should retry a response with the given `status`,
+/// having already made `attempt` prior attempts. Only `429` and `5xx` responses are
+/// retryable, and only up to `policy.max_retries` times.
+fn should_retry(status: StatusCode, attempt: u32, policy: &RetryPolicy) -> bool {
+    let is_retryable = status.as_u16() == 429 || status.is_server_error();
+    is_retryable && attempt < policy.max_retries
+}
+
+/// Reads the `Retry-After` header, if present, as a number of seconds to wait.
+fn retry_after_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header_value = headers.get(reqwest::header::RETRY_AFTER)?;
+    let seconds: u64 = header_value.to_str().ok()?.parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Computes a jittered exponential backoff delay for the given retry attempt (0-indexed).
+fn backoff_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let exponential = policy.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+    let capped = std::cmp::min(exponential, policy.max_delay);
+    let jitter_ms = rand::thread_rng().gen_range(0..=capped.as_millis() as u64);
+    Duration::from_millis(jitter_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_default_retry_policy() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_retries, 3);
+        assert_eq!(policy.base_delay, Duration::from_millis(500));
+        assert_eq!(policy.max_delay, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_backoff_delay_is_capped_by_max_delay() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(2),
+        };
+
+        for attempt in 0..10 {
+            assert!(backoff_delay(&policy, attempt) <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn test_should_retry_on_429_and_5xx() {
+        let policy = RetryPolicy::default();
+        assert!(should_retry(StatusCode::TOO_MANY_REQUESTS, 0, &policy));
+        assert!(should_retry(StatusCode::INTERNAL_SERVER_ERROR, 0, &policy));
+        assert!(should_retry(StatusCode::SERVICE_UNAVAILABLE, 0, &policy));
+    }
+
+    #[test]
+    fn test_should_retry_false_for_non_retryable_status() {
+        let policy = RetryPolicy::default();
+        assert!(!should_retry(StatusCode::OK, 0, &policy));
+        assert!(!should_retry(StatusCode::BAD_REQUEST, 0, &policy));
+        assert!(!should_retry(StatusCode::NOT_FOUND, 0, &policy));
+    }
+
+    #[test]
+    fn test_should_retry_stops_once_max_retries_reached() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            ..RetryPolicy::default()
+        };
+
+        assert!(should_retry(StatusCode::TOO_MANY_REQUESTS, 0, &policy));
+        assert!(should_retry(StatusCode::TOO_MANY_REQUESTS, 1, &policy));
+        assert!(!should_retry(StatusCode::TOO_MANY_REQUESTS, 2, &policy));
+    }
+
+    #[test]
+    fn test_retry_after_delay_parses_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(retry_after_delay(&headers), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_retry_after_delay_missing_header() {
+        let headers = HeaderMap::new();
+        assert_eq!(retry_after_delay(&headers), None);
+    }
+}